@@ -0,0 +1,181 @@
+//! Bearer-token caching and refresh.
+//!
+//! Treats the Pi Network access token like any other short-lived credential instead of a
+//! static forever-secret: [`CachingTokenProvider`] holds the active [`Token`] and its expiry,
+//! and transparently refreshes it once it comes within [`CachingTokenProvider::refresh_skew`]
+//! of expiring. Callers racing an expired token coalesce onto a single in-flight refresh
+//! rather than each firing their own request.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use tokio::sync::{Mutex, RwLock};
+
+use crate::errors::PiError;
+
+/// An access token and the instant it stops being valid.
+#[derive(Debug, Clone)]
+pub struct Token {
+    pub value: String,
+    pub expires_at: Instant,
+}
+
+impl Token {
+    /// `true` if the token is valid for at least `skew` longer.
+    fn is_fresh(&self, skew: Duration) -> bool {
+        Instant::now() + skew < self.expires_at
+    }
+}
+
+/// Supplies a bearer token for outbound requests, refreshing it before it expires.
+#[async_trait]
+pub trait TokenProvider: Send + Sync {
+    async fn token(&self) -> crate::Result<Arc<Token>>;
+}
+
+/// Exchanges credentials for a fresh [`Token`]. Implemented by whatever actually talks to
+/// the Pi Network auth endpoint; [`CachingTokenProvider`] only owns the caching policy.
+#[async_trait]
+pub trait TokenExchange: Send + Sync {
+    async fn exchange(&self) -> crate::Result<Token>;
+}
+
+/// How close to expiry a token must be before it's proactively refreshed.
+const DEFAULT_REFRESH_SKEW: Duration = Duration::from_secs(60);
+
+/// Caches the token returned by a [`TokenExchange`] and refreshes it once it is within
+/// `refresh_skew` of expiring. Concurrent callers that observe an expired token coalesce
+/// onto a single in-flight refresh rather than each starting their own.
+pub struct CachingTokenProvider<E> {
+    exchange: E,
+    refresh_skew: Duration,
+    cached: RwLock<Option<Arc<Token>>>,
+    refresh_lock: Mutex<()>,
+}
+
+impl<E: TokenExchange> CachingTokenProvider<E> {
+    pub fn new(exchange: E) -> Self {
+        Self::with_refresh_skew(exchange, DEFAULT_REFRESH_SKEW)
+    }
+
+    pub fn with_refresh_skew(exchange: E, refresh_skew: Duration) -> Self {
+        Self {
+            exchange,
+            refresh_skew,
+            cached: RwLock::new(None),
+            refresh_lock: Mutex::new(()),
+        }
+    }
+
+    async fn cached_if_fresh(&self) -> Option<Arc<Token>> {
+        let cached = self.cached.read().await;
+        cached
+            .as_ref()
+            .filter(|token| token.is_fresh(self.refresh_skew))
+            .cloned()
+    }
+
+    async fn refresh(&self) -> crate::Result<Arc<Token>> {
+        let _permit = self.refresh_lock.lock().await;
+
+        // Another caller may have refreshed while we were waiting for the lock.
+        if let Some(token) = self.cached_if_fresh().await {
+            return Ok(token);
+        }
+
+        let token = Arc::new(
+            self.exchange
+                .exchange()
+                .await
+                .map_err(|e| PiError::Authentication(e.to_string()))?,
+        );
+        *self.cached.write().await = Some(token.clone());
+        Ok(token)
+    }
+}
+
+#[async_trait]
+impl<E: TokenExchange> TokenProvider for CachingTokenProvider<E> {
+    async fn token(&self) -> crate::Result<Arc<Token>> {
+        if let Some(token) = self.cached_if_fresh().await {
+            return Ok(token);
+        }
+
+        self.refresh().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    struct CountingExchange {
+        calls: AtomicU32,
+        ttl: Duration,
+    }
+
+    #[async_trait]
+    impl TokenExchange for CountingExchange {
+        async fn exchange(&self) -> crate::Result<Token> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(Token {
+                value: "fresh-token".to_string(),
+                expires_at: Instant::now() + self.ttl,
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn reuses_a_fresh_token() {
+        let provider = CachingTokenProvider::new(CountingExchange {
+            calls: AtomicU32::new(0),
+            ttl: Duration::from_secs(3600),
+        });
+
+        let first = provider.token().await.unwrap();
+        let second = provider.token().await.unwrap();
+
+        assert_eq!(first.value, second.value);
+        assert_eq!(provider.exchange.calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn refreshes_once_within_the_skew_window() {
+        let provider = CachingTokenProvider::with_refresh_skew(
+            CountingExchange {
+                calls: AtomicU32::new(0),
+                ttl: Duration::from_millis(10),
+            },
+            Duration::from_secs(1),
+        );
+
+        provider.token().await.unwrap();
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        provider.token().await.unwrap();
+
+        assert_eq!(provider.exchange.calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn concurrent_callers_coalesce_onto_one_refresh() {
+        let provider = Arc::new(CachingTokenProvider::new(CountingExchange {
+            calls: AtomicU32::new(0),
+            ttl: Duration::from_secs(3600),
+        }));
+
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let provider = provider.clone();
+                tokio::spawn(async move { provider.token().await.unwrap() })
+            })
+            .collect();
+
+        for handle in handles {
+            handle.await.unwrap();
+        }
+
+        assert_eq!(provider.exchange.calls.load(Ordering::SeqCst), 1);
+    }
+}
@@ -0,0 +1,237 @@
+//! Per-host circuit breaking for the HTTP client.
+//!
+//! A [`Breakers`] instance tracks one [`Breaker`] per host authority (`host:port`) so a
+//! single failing Pi Network endpoint doesn't get hammered with retries while every other
+//! host keeps working normally.
+
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::RwLock;
+use std::time::{Duration, Instant};
+
+use dashmap::DashMap;
+use url::Url;
+
+use crate::errors::PiError;
+
+/// Which HTTP status codes count as a failure for a given request.
+///
+/// Some endpoints return 4xx for conditions that are entirely expected (e.g. a lookup that
+/// returns 404 for "not found"), and those shouldn't trip the breaker the same way a string
+/// of 500s or timeouts would.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BreakerStrategy {
+    /// Anything outside the 2xx range counts as a failure.
+    Require2XX,
+    /// 4xx responses up to and including 401 are tolerated; anything above trips the breaker.
+    Allow401AndBelow,
+    /// 4xx responses up to and including 404 are tolerated; anything above trips the breaker.
+    Allow404AndBelow,
+}
+
+impl BreakerStrategy {
+    /// Returns `true` if `status` should count as a breaker failure under this strategy.
+    pub fn is_failure(&self, status: u16) -> bool {
+        match self {
+            BreakerStrategy::Require2XX => !(200..300).contains(&status),
+            BreakerStrategy::Allow401AndBelow => status > 401,
+            BreakerStrategy::Allow404AndBelow => status > 404,
+        }
+    }
+}
+
+/// Upper bound on a tripped breaker's cooldown. The cooldown doubles with each consecutive
+/// trip, at most seven times (128x the base cooldown), and never exceeds this cap.
+const MAX_COOLDOWN: Duration = Duration::from_secs(300);
+
+/// How long a half-open probe provisionally holds the breaker tripped for, so a second
+/// caller arriving before the probe's outcome is recorded doesn't also slip through.
+const PROBE_HOLD: Duration = Duration::from_secs(10);
+
+#[derive(Debug)]
+struct Breaker {
+    failures: AtomicU32,
+    trips: AtomicU32,
+    tripped_until: RwLock<Option<Instant>>,
+}
+
+impl Breaker {
+    fn new() -> Self {
+        Self {
+            failures: AtomicU32::new(0),
+            trips: AtomicU32::new(0),
+            tripped_until: RwLock::new(None),
+        }
+    }
+
+    /// `true` if the host is untripped, or the cooldown has just elapsed. In the latter
+    /// case this is a half-open probe: only the single caller that observes the transition
+    /// gets `true` back, everyone else is held off until that probe's outcome is recorded.
+    fn should_try(&self) -> bool {
+        let now = Instant::now();
+
+        {
+            let tripped_until = self.tripped_until.read().unwrap();
+            match *tripped_until {
+                None => return true,
+                Some(until) if now < until => return false,
+                Some(_) => {}
+            }
+        }
+
+        // Cooldown has elapsed for someone; re-check under the write lock and, if we're the
+        // one to observe it, provisionally extend the trip so concurrent callers are held
+        // off until success()/fail() records this probe's outcome.
+        let mut tripped_until = self.tripped_until.write().unwrap();
+        match *tripped_until {
+            Some(until) if now >= until => {
+                *tripped_until = Some(now + PROBE_HOLD);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn success(&self) {
+        self.failures.store(0, Ordering::SeqCst);
+        self.trips.store(0, Ordering::SeqCst);
+        *self.tripped_until.write().unwrap() = None;
+    }
+
+    fn fail(&self, threshold: u32, base_cooldown: Duration) {
+        let failures = self.failures.fetch_add(1, Ordering::SeqCst) + 1;
+
+        // A failed half-open probe re-trips immediately, regardless of the raw failure
+        // count, otherwise should_try's provisional hold would never get corrected.
+        let was_probing = self.tripped_until.read().unwrap().is_some();
+        if failures < threshold && !was_probing {
+            return;
+        }
+
+        self.failures.store(0, Ordering::SeqCst);
+        let trip = self.trips.fetch_add(1, Ordering::SeqCst) + 1;
+        let cooldown = base_cooldown
+            .saturating_mul(1u32 << trip.min(8).saturating_sub(1))
+            .min(MAX_COOLDOWN);
+        *self.tripped_until.write().unwrap() = Some(Instant::now() + cooldown);
+    }
+}
+
+/// Derives the `host:port` authority a breaker is keyed by from a request URL.
+pub fn authority_of(url: &Url) -> String {
+    match (url.host_str(), url.port_or_known_default()) {
+        (Some(host), Some(port)) => format!("{host}:{port}"),
+        (Some(host), None) => host.to_string(),
+        (None, _) => String::new(),
+    }
+}
+
+/// Tracks a [`Breaker`] per host authority.
+#[derive(Debug)]
+pub struct Breakers {
+    breakers: DashMap<String, Breaker>,
+    threshold: u32,
+    base_cooldown: Duration,
+}
+
+impl Breakers {
+    pub fn new(threshold: u32, base_cooldown: Duration) -> Self {
+        Self {
+            breakers: DashMap::new(),
+            threshold,
+            base_cooldown,
+        }
+    }
+
+    /// Returns `Ok(())` if a request to `authority` may proceed, or
+    /// `Err(PiError::CircuitOpen)` if the breaker for that host is tripped.
+    pub fn guard(&self, authority: &str) -> crate::Result<()> {
+        let should_try = self
+            .breakers
+            .get(authority)
+            .map(|breaker| breaker.should_try())
+            .unwrap_or(true);
+
+        if should_try {
+            Ok(())
+        } else {
+            Err(PiError::CircuitOpen {
+                authority: authority.to_string(),
+            })
+        }
+    }
+
+    /// Records the outcome of a request to `authority` under the given strategy.
+    pub fn record(&self, authority: &str, status: u16, strategy: BreakerStrategy) {
+        let breaker = self
+            .breakers
+            .entry(authority.to_string())
+            .or_insert_with(Breaker::new);
+
+        if strategy.is_failure(status) {
+            breaker.fail(self.threshold, self.base_cooldown);
+        } else {
+            breaker.success();
+        }
+    }
+}
+
+impl Default for Breakers {
+    fn default() -> Self {
+        Self::new(
+            crate::config::DEFAULT_BREAKER_THRESHOLD,
+            crate::config::DEFAULT_BREAKER_BASE_COOLDOWN,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trips_after_threshold_failures() {
+        let breakers = Breakers::new(3, Duration::from_millis(50));
+        for _ in 0..2 {
+            breakers.record("api.minepi.com:443", 500, BreakerStrategy::Require2XX);
+        }
+        assert!(breakers.guard("api.minepi.com:443").is_ok());
+
+        breakers.record("api.minepi.com:443", 500, BreakerStrategy::Require2XX);
+        assert!(breakers.guard("api.minepi.com:443").is_err());
+    }
+
+    #[test]
+    fn success_resets_failure_count() {
+        let breakers = Breakers::new(2, Duration::from_millis(50));
+        breakers.record("api.minepi.com:443", 500, BreakerStrategy::Require2XX);
+        breakers.record("api.minepi.com:443", 200, BreakerStrategy::Require2XX);
+        breakers.record("api.minepi.com:443", 500, BreakerStrategy::Require2XX);
+        assert!(breakers.guard("api.minepi.com:443").is_ok());
+    }
+
+    #[test]
+    fn only_one_caller_gets_the_half_open_probe() {
+        let breakers = Breakers::new(1, Duration::from_millis(10));
+        breakers.record("api.minepi.com:443", 500, BreakerStrategy::Require2XX);
+        assert!(breakers.guard("api.minepi.com:443").is_err());
+
+        std::thread::sleep(Duration::from_millis(20));
+
+        // Cooldown has elapsed: exactly one of these should be let through as the probe.
+        assert!(breakers.guard("api.minepi.com:443").is_ok());
+        assert!(breakers.guard("api.minepi.com:443").is_err());
+    }
+
+    #[test]
+    fn allow_404_and_below_tolerates_expected_misses() {
+        let breakers = Breakers::new(1, Duration::from_millis(50));
+        breakers.record("api.minepi.com:443", 404, BreakerStrategy::Allow404AndBelow);
+        assert!(breakers.guard("api.minepi.com:443").is_ok());
+    }
+
+    #[test]
+    fn authority_of_includes_default_port() {
+        let url = Url::parse("https://api.minepi.com/v2/payments").unwrap();
+        assert_eq!(authority_of(&url), "api.minepi.com:443");
+    }
+}
@@ -0,0 +1,124 @@
+//! The Pi Network HTTP client.
+
+use url::Url;
+
+use crate::breaker::{self, BreakerStrategy, Breakers};
+use crate::config::ClientConfig;
+use crate::errors::PiError;
+
+/// Wraps a `reqwest::Client` with the per-host circuit breaker configured via
+/// [`ClientConfig`] so a failing host gets short-circuited instead of hammered.
+pub struct Client {
+    config: ClientConfig,
+    http: reqwest::Client,
+    breakers: Breakers,
+}
+
+impl Client {
+    pub fn new(config: ClientConfig) -> crate::Result<Self> {
+        let mut builder = reqwest::ClientBuilder::new()
+            .timeout(config.timeout)
+            .user_agent(config.user_agent.clone());
+
+        if let Some(identity) = config.client_identity.clone() {
+            builder = builder.identity(identity);
+        }
+
+        let http = builder.build().map_err(PiError::Http)?;
+
+        let breakers = Breakers::new(config.breaker_threshold, config.breaker_base_cooldown);
+
+        Ok(Self {
+            config,
+            http,
+            breakers,
+        })
+    }
+
+    pub fn config(&self) -> &ClientConfig {
+        &self.config
+    }
+
+    /// The underlying `reqwest::Client`, for call sites that need to build their own requests.
+    pub fn http(&self) -> &reqwest::Client {
+        &self.http
+    }
+
+    /// The bearer token to send with a request: the cached/refreshed token from a configured
+    /// `TokenProvider`, or the static `api_key` when none is set.
+    async fn bearer_token(&self) -> crate::Result<String> {
+        match &self.config.token_provider {
+            Some(provider) => Ok(provider.token().await?.value.clone()),
+            None => Ok(self.config.api_key.clone()),
+        }
+    }
+
+    /// Sends `request` against `url`, short-circuiting with `PiError::CircuitOpen` if that
+    /// host's breaker is tripped, and recording the outcome against `strategy` otherwise.
+    pub async fn send(
+        &self,
+        request: reqwest::RequestBuilder,
+        url: &Url,
+        strategy: BreakerStrategy,
+    ) -> crate::Result<reqwest::Response> {
+        let authority = breaker::authority_of(url);
+        self.breakers.guard(&authority)?;
+
+        let token = self.bearer_token().await?;
+        let request = request.bearer_auth(token);
+
+        match request.send().await {
+            Ok(response) => {
+                self.breakers
+                    .record(&authority, response.status().as_u16(), strategy);
+                Ok(response)
+            }
+            Err(e) => {
+                // No status to record against a transport-level failure (timeout, connect
+                // refused, etc); treat it as a failure for breaker purposes regardless of
+                // the strategy's status-range tolerance.
+                self.breakers.record(&authority, 599, strategy);
+                Err(PiError::Http(e))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::{CachingTokenProvider, Token, TokenExchange};
+    use async_trait::async_trait;
+    use std::time::{Duration, Instant};
+
+    struct StaticExchange(&'static str);
+
+    #[async_trait]
+    impl TokenExchange for StaticExchange {
+        async fn exchange(&self) -> crate::Result<Token> {
+            Ok(Token {
+                value: self.0.to_string(),
+                expires_at: Instant::now() + Duration::from_secs(3600),
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn bearer_token_falls_back_to_the_static_api_key() {
+        let config = ClientConfig::new("static-key".to_string()).unwrap();
+        let client = Client::new(config).unwrap();
+
+        assert_eq!(client.bearer_token().await.unwrap(), "static-key");
+    }
+
+    #[tokio::test]
+    async fn bearer_token_prefers_a_configured_token_provider() {
+        let config = ClientConfig::builder("static-key".to_string())
+            .token_provider(CachingTokenProvider::new(StaticExchange("provider-token")))
+            .build()
+            .unwrap();
+        let client = Client::new(config).unwrap();
+
+        assert_eq!(client.bearer_token().await.unwrap(), "provider-token");
+    }
+}
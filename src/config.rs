@@ -1,13 +1,47 @@
+use std::path::PathBuf;
+use std::sync::Arc;
 use std::time::Duration;
+
+use reqwest::Identity;
 use url::Url;
 
-#[derive(Debug, Clone)]
+use crate::auth::TokenProvider;
+
+/// Default number of consecutive failures before a host's breaker trips.
+pub(crate) const DEFAULT_BREAKER_THRESHOLD: u32 = 10;
+/// Default cooldown for a host's first trip; later trips back off from here.
+pub(crate) const DEFAULT_BREAKER_BASE_COOLDOWN: Duration = Duration::from_secs(5);
+
+#[derive(Clone)]
 pub struct ClientConfig {
     pub api_key: String,
     pub base_url: Url,
     pub timeout: Duration,
     pub retry_config: RetryConfig,
     pub user_agent: String,
+    pub breaker_threshold: u32,
+    pub breaker_base_cooldown: Duration,
+    /// Client certificate presented for mutual TLS, if the gateway requires one.
+    pub client_identity: Option<Identity>,
+    /// Supplies and refreshes the bearer token for outbound requests. `None` means
+    /// `api_key` is sent as-is, with no caching or refresh.
+    pub token_provider: Option<Arc<dyn TokenProvider>>,
+}
+
+impl std::fmt::Debug for ClientConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ClientConfig")
+            .field("api_key", &"<redacted>")
+            .field("base_url", &self.base_url)
+            .field("timeout", &self.timeout)
+            .field("retry_config", &self.retry_config)
+            .field("user_agent", &self.user_agent)
+            .field("breaker_threshold", &self.breaker_threshold)
+            .field("breaker_base_cooldown", &self.breaker_base_cooldown)
+            .field("client_identity", &self.client_identity.is_some())
+            .field("token_provider", &self.token_provider.is_some())
+            .finish()
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -30,6 +64,10 @@ impl ClientConfig {
             timeout: Duration::from_secs(30),
             retry_config: RetryConfig::default(),
             user_agent: format!("pi-rust/{}", env!("CARGO_PKG_VERSION")),
+            breaker_threshold: DEFAULT_BREAKER_THRESHOLD,
+            breaker_base_cooldown: DEFAULT_BREAKER_BASE_COOLDOWN,
+            client_identity: None,
+            token_provider: None,
         })
     }
 
@@ -49,14 +87,42 @@ impl Default for RetryConfig {
     }
 }
 
+/// A client certificate staged on the builder, not yet parsed into a [`reqwest::Identity`].
+enum PendingIdentity {
+    Pem { cert: Vec<u8>, key: Vec<u8> },
+    Path { cert: PathBuf, key: PathBuf },
+}
+
+/// Parses a client certificate and private key into a [`reqwest::Identity`].
+///
+/// `reqwest::Identity` construction is specific to the TLS backend in use: `from_pem` (a
+/// combined cert+key PEM) only exists under the `rustls-tls` feature, while `from_pkcs8_pem`
+/// (separate cert and key buffers) is what `native-tls` offers. This crate must be built
+/// with one of those two reqwest features enabled for mTLS support to compile.
+#[cfg(feature = "rustls-tls")]
+fn parse_identity(cert: &[u8], key: &[u8]) -> crate::Result<Identity> {
+    let mut pem = cert.to_vec();
+    pem.extend_from_slice(key);
+    Identity::from_pem(&pem)
+        .map_err(|e| crate::PiError::Configuration(format!("invalid client certificate: {e}")))
+}
+
+#[cfg(all(feature = "native-tls", not(feature = "rustls-tls")))]
+fn parse_identity(cert: &[u8], key: &[u8]) -> crate::Result<Identity> {
+    Identity::from_pkcs8_pem(cert, key)
+        .map_err(|e| crate::PiError::Configuration(format!("invalid client certificate: {e}")))
+}
+
 pub struct ClientConfigBuilder {
     config: ClientConfig,
+    pending_identity: Option<PendingIdentity>,
 }
 
 impl ClientConfigBuilder {
     pub fn new(api_key: String) -> Self {
         Self {
             config: ClientConfig::new(api_key).expect("Invalid API key"),
+            pending_identity: None,
         }
     }
 
@@ -70,8 +136,76 @@ impl ClientConfigBuilder {
         self
     }
 
-    pub fn build(self) -> ClientConfig {
-        self.config
+    pub fn retry_config(mut self, retry_config: RetryConfig) -> Self {
+        self.config.retry_config = retry_config;
+        self
+    }
+
+    /// Sets the number of consecutive failures that trips a host's circuit breaker.
+    pub fn breaker_threshold(mut self, threshold: u32) -> Self {
+        self.config.breaker_threshold = threshold;
+        self
+    }
+
+    /// Sets the cooldown applied the first time a host's circuit breaker trips; later
+    /// trips back off further, capped internally.
+    pub fn breaker_base_cooldown(mut self, cooldown: Duration) -> Self {
+        self.config.breaker_base_cooldown = cooldown;
+        self
+    }
+
+    /// Stages a PEM-encoded client certificate and private key for mutual TLS. The PEM is
+    /// parsed and validated when [`build`](Self::build) is called.
+    pub fn client_cert_pem(mut self, cert: impl Into<Vec<u8>>, key: impl Into<Vec<u8>>) -> Self {
+        self.pending_identity = Some(PendingIdentity::Pem {
+            cert: cert.into(),
+            key: key.into(),
+        });
+        self
+    }
+
+    /// Stages a PEM-encoded client certificate and private key, each loaded from its own
+    /// file, for mutual TLS. Read and validated when [`build`](Self::build) is called.
+    pub fn client_cert_path(mut self, cert_path: impl Into<PathBuf>, key_path: impl Into<PathBuf>) -> Self {
+        self.pending_identity = Some(PendingIdentity::Path {
+            cert: cert_path.into(),
+            key: key_path.into(),
+        });
+        self
+    }
+
+    /// Plugs in a custom [`TokenProvider`] instead of sending `api_key` as a static bearer
+    /// token, e.g. to cache and refresh a short-lived credential from another source.
+    pub fn token_provider(mut self, provider: impl TokenProvider + 'static) -> Self {
+        self.config.token_provider = Some(Arc::new(provider));
+        self
+    }
+
+    pub fn build(mut self) -> crate::Result<ClientConfig> {
+        if let Some(pending) = self.pending_identity.take() {
+            let (cert, key) = match pending {
+                PendingIdentity::Pem { cert, key } => (cert, key),
+                PendingIdentity::Path { cert, key } => {
+                    let cert_pem = std::fs::read(&cert).map_err(|e| {
+                        crate::PiError::Configuration(format!(
+                            "failed to read client certificate at {}: {e}",
+                            cert.display()
+                        ))
+                    })?;
+                    let key_pem = std::fs::read(&key).map_err(|e| {
+                        crate::PiError::Configuration(format!(
+                            "failed to read client certificate key at {}: {e}",
+                            key.display()
+                        ))
+                    })?;
+                    (cert_pem, key_pem)
+                }
+            };
+
+            self.config.client_identity = Some(parse_identity(&cert, &key)?);
+        }
+
+        Ok(self.config)
     }
 }
 
@@ -96,7 +230,16 @@ mod tests {
     fn test_builder_pattern() {
         let config = ClientConfig::builder("test-key".to_string())
             .timeout(Duration::from_secs(60))
-            .build();
+            .build()
+            .unwrap();
         assert_eq!(config.timeout, Duration::from_secs(60));
     }
+
+    #[test]
+    fn test_invalid_client_cert_pem_fails() {
+        let result = ClientConfig::builder("test-key".to_string())
+            .client_cert_pem(b"not a cert".to_vec(), b"not a key".to_vec())
+            .build();
+        assert!(result.is_err());
+    }
 }
\ No newline at end of file
@@ -13,7 +13,11 @@ pub enum PiError {
     PiNetwork {
         error_name: String,
         error_message: String,
-        payment: Option<crate::models::PaymentDto>,
+        payment: Option<Box<crate::models::PaymentDto>>,
+        /// The HTTP status the error body was parsed from, when known.
+        status: Option<u16>,
+        /// Delay requested by the server's `Retry-After` header, when present.
+        retry_after: Option<Duration>,
     },
 
     #[error("Authentication failed: {0}")]
@@ -30,6 +34,9 @@ pub enum PiError {
 
     #[error("Timeout occurred after {duration:?}")]
     Timeout { duration: Duration },
+
+    #[error("Circuit breaker open for {authority}")]
+    CircuitOpen { authority: String },
 }
 
 pub type Result<T> = std::result::Result<T, PiError>;
\ No newline at end of file
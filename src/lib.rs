@@ -0,0 +1,11 @@
+pub mod auth;
+pub mod breaker;
+pub mod client;
+pub mod config;
+pub mod errors;
+pub mod models;
+pub mod retry;
+
+pub use client::Client;
+pub use config::{ClientConfig, ClientConfigBuilder, RetryConfig};
+pub use errors::{PiError, Result};
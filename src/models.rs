@@ -0,0 +1,37 @@
+//! Data transfer objects returned by the Pi Network Platform API.
+
+use serde::{Deserialize, Serialize};
+
+/// A payment as returned by the Pi Network Platform API.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaymentDto {
+    pub identifier: String,
+    pub user_uid: String,
+    pub amount: f64,
+    pub memo: String,
+    pub metadata: serde_json::Value,
+    pub from_address: String,
+    pub to_address: String,
+    pub status: PaymentStatusDto,
+    pub transaction: Option<TransactionDto>,
+    pub created_at: String,
+}
+
+/// The lifecycle state of a [`PaymentDto`] as tracked by the Platform API.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaymentStatusDto {
+    pub developer_approved: bool,
+    pub transaction_verified: bool,
+    pub developer_completed: bool,
+    pub cancelled: bool,
+    pub user_cancelled: bool,
+}
+
+/// The on-chain Stellar transaction backing a [`PaymentDto`], once one has been submitted.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransactionDto {
+    pub txid: String,
+    pub verified: bool,
+    #[serde(rename = "_link")]
+    pub link: String,
+}
@@ -0,0 +1,185 @@
+//! Retry executor for transient request failures.
+//!
+//! Wraps an operation future and, on a retryable [`PiError`], sleeps and re-issues it up to
+//! [`RetryConfig::max_retries`] times. Backoff follows a capped exponential curve with full
+//! jitter so concurrent clients don't retry in lockstep; a server-provided `Retry-After`
+//! header always takes priority over the computed delay.
+
+use std::future::Future;
+use std::time::Duration;
+
+use rand::Rng;
+
+use crate::config::RetryConfig;
+use crate::errors::PiError;
+
+/// Runs `operation` and retries on a retryable error until it succeeds or it has been
+/// retried `config.max_retries` times (at most `max_retries + 1` calls in total).
+pub async fn execute<T, F, Fut>(config: &RetryConfig, mut operation: F) -> crate::Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = crate::Result<T>>,
+{
+    let mut attempt = 0;
+
+    loop {
+        match operation().await {
+            Ok(value) => return Ok(value),
+            Err(error) if attempt < config.max_retries && is_retryable(&error) => {
+                let delay = retry_after(&error).unwrap_or_else(|| backoff_delay(config, attempt));
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+            }
+            Err(error) => return Err(error),
+        }
+    }
+}
+
+/// Returns `true` if `error` represents a transient condition worth retrying.
+pub fn is_retryable(error: &PiError) -> bool {
+    match error {
+        PiError::Timeout { .. } => true,
+        PiError::Http(e) => e.is_timeout() || e.is_connect(),
+        PiError::PiNetwork { status, .. } => {
+            matches!(status, Some(429) | Some(502) | Some(503) | Some(504))
+        }
+        PiError::Authentication(_) | PiError::Configuration(_) | PiError::InsufficientBalance { .. } => {
+            false
+        }
+        PiError::Json(_) | PiError::Stellar(_) | PiError::CircuitOpen { .. } => false,
+    }
+}
+
+fn retry_after(error: &PiError) -> Option<Duration> {
+    match error {
+        PiError::PiNetwork { retry_after, .. } => *retry_after,
+        _ => None,
+    }
+}
+
+/// `min(max_delay, initial_delay * backoff_factor^attempt)` with full jitter applied.
+fn backoff_delay(config: &RetryConfig, attempt: u32) -> Duration {
+    // Clamp in f64 seconds before building a Duration: at high attempt counts
+    // initial_delay * backoff_factor^attempt overflows what Duration can represent, and
+    // Duration::mul_f64/from_secs_f64 panic rather than saturate.
+    let exponent = config.backoff_factor.powi(attempt as i32);
+    let computed_secs = (config.initial_delay.as_secs_f64() * exponent.max(0.0))
+        .min(config.max_delay.as_secs_f64());
+    let computed = Duration::from_secs_f64(computed_secs);
+
+    let jitter_ms = rand::thread_rng().gen_range(0..=computed.as_millis().max(1) as u64);
+    Duration::from_millis(jitter_ms)
+}
+
+/// Parses a `Retry-After` header value, which is either a number of seconds or an HTTP-date.
+pub fn parse_retry_after(value: &str) -> Option<Duration> {
+    if let Ok(seconds) = value.trim().parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let target = httpdate::parse_http_date(value.trim()).ok()?;
+    target
+        .duration_since(std::time::SystemTime::now())
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    #[test]
+    fn authentication_errors_are_terminal() {
+        assert!(!is_retryable(&PiError::Authentication("bad token".to_string())));
+    }
+
+    #[test]
+    fn timeout_errors_are_retryable() {
+        assert!(is_retryable(&PiError::Timeout {
+            duration: Duration::from_secs(30)
+        }));
+    }
+
+    #[test]
+    fn pi_network_429_is_retryable_but_400_is_not() {
+        let rate_limited = PiError::PiNetwork {
+            error_name: "rate_limited".to_string(),
+            error_message: "slow down".to_string(),
+            payment: None,
+            status: Some(429),
+            retry_after: None,
+        };
+        assert!(is_retryable(&rate_limited));
+
+        let bad_request = PiError::PiNetwork {
+            error_name: "invalid_request".to_string(),
+            error_message: "bad input".to_string(),
+            payment: None,
+            status: Some(400),
+            retry_after: None,
+        };
+        assert!(!is_retryable(&bad_request));
+    }
+
+    #[test]
+    fn parse_retry_after_accepts_seconds_and_http_date() {
+        assert_eq!(parse_retry_after("120"), Some(Duration::from_secs(120)));
+        assert!(parse_retry_after("Wed, 21 Oct 2099 07:28:00 GMT").is_some());
+        assert_eq!(parse_retry_after("not-a-value"), None);
+    }
+
+    #[test]
+    fn backoff_delay_saturates_instead_of_panicking_at_high_attempt_counts() {
+        let config = RetryConfig {
+            max_retries: 1000,
+            initial_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(10),
+            backoff_factor: 2.0,
+        };
+
+        let delay = backoff_delay(&config, 70);
+
+        assert!(delay <= config.max_delay);
+    }
+
+    #[tokio::test]
+    async fn execute_retries_until_success() {
+        let config = RetryConfig {
+            max_retries: 3,
+            initial_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(5),
+            backoff_factor: 2.0,
+        };
+        let attempts = AtomicU32::new(0);
+
+        let result = execute(&config, || async {
+            if attempts.fetch_add(1, Ordering::SeqCst) < 2 {
+                Err(PiError::Timeout {
+                    duration: Duration::from_millis(1),
+                })
+            } else {
+                Ok(42)
+            }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(result, 42);
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn execute_gives_up_on_terminal_errors() {
+        let config = RetryConfig::default();
+        let attempts = AtomicU32::new(0);
+
+        let result: crate::Result<()> = execute(&config, || async {
+            attempts.fetch_add(1, Ordering::SeqCst);
+            Err(PiError::Authentication("nope".to_string()))
+        })
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+    }
+}